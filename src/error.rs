@@ -3,10 +3,15 @@ use thiserror::Error;
 
 #[derive(Error, Debug, Clone)]
 pub enum Error {
+    #[cfg(windows)]
     #[error(transparent)]
     Windows(#[from] windows::core::Error),
     #[error(transparent)]
     Unix(#[from] Errno),
+    #[error("key code has no corresponding native virtual-key code")]
+    NoNativeKeyCode,
+    #[error("HidType is driven by HidMonitor::spawn; call HidMonitor::stop before enable/disable")]
+    Spawned,
 }
 
 pub type Result<T> = std::result::Result<T, Error>;
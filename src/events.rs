@@ -0,0 +1,67 @@
+use crate::key_code::KeyCode;
+
+/// A decoded keyboard event, parsed out of a backend-specific representation (the raw
+/// `WPARAM`/`LPARAM` pair delivered to a low-level keyboard hook, a `RAWKEYBOARD` struct, or a
+/// Linux `evdev` `input_event`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeyboardEvent {
+    KeyDown {
+        key: KeyCode,
+        scan_code: u32,
+        flags: u32,
+        time: u32,
+    },
+    KeyUp {
+        key: KeyCode,
+        scan_code: u32,
+        flags: u32,
+        time: u32,
+    },
+}
+
+/// Which physical mouse button a [`MouseEvent::Button`] refers to
+///
+/// Normalized across backends: `Backend::Hook`'s `WM_*BUTTON*` messages, `Backend::RawInput`'s
+/// `RI_MOUSE_*` flags, and Linux `evdev`'s `BTN_*` codes all decode to the same variants here, so
+/// callers don't need to special-case a particular backend.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseButton {
+    Left,
+    Right,
+    Middle,
+    /// The first extra ("back") button; only reachable via `Backend::Hook`'s `WM_XBUTTON*`
+    X1,
+    /// The second extra ("forward") button; only reachable via `Backend::Hook`'s `WM_XBUTTON*`
+    X2,
+}
+
+/// Whether a [`MouseEvent::Button`] is a press or a release
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ButtonState {
+    Down,
+    Up,
+}
+
+/// A decoded mouse event, parsed out of a backend-specific representation (the raw
+/// `WPARAM`/`LPARAM` pair delivered to a low-level mouse hook, a `RAWMOUSE` struct, or a Linux
+/// `evdev` `input_event`)
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum MouseEvent {
+    /// Relative `x`/`y` movement since the previous `Move` event
+    ///
+    /// Reported as deltas on every backend, including `Backend::Hook`, which only ever receives
+    /// absolute cursor position and so tracks the previous position itself to derive one.
+    Move {
+        x: i32,
+        y: i32,
+    },
+    Button {
+        button: MouseButton,
+        state: ButtonState,
+        time: u32,
+    },
+    /// `delta` is the signed wheel rotation amount, in multiples of `WHEEL_DELTA` (120)
+    Wheel {
+        delta: i16,
+    },
+}
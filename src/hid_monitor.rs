@@ -1,9 +1,26 @@
 use std::sync::{Arc, Mutex};
 
+#[cfg(windows)]
+use std::sync::mpsc;
+#[cfg(windows)]
+use std::thread::JoinHandle;
+
 use crate::globals::GlobalCallback;
+use crate::hotkey::{HotkeyDispatcher, HotkeyId, Modifiers};
+use crate::key_code::KeyCode;
+#[cfg(windows)]
+use crate::raw_input;
 use crate::traits::Call;
+#[cfg(unix)]
+use crate::unix;
+#[cfg(windows)]
 use crate::windows;
+#[cfg(windows)]
+use crate::Error;
 use crate::Result;
+#[cfg(windows)]
+use ::windows::Win32::Foundation::HWND;
+#[cfg(windows)]
 use ::windows::Win32::UI::WindowsAndMessaging::HHOOK;
 
 #[derive(Clone, Copy)]
@@ -12,76 +29,233 @@ pub enum HidType {
     Mouse,
 }
 
+/// Selects how a [`HidMonitor`] observes HID input
+///
+/// Windows-only: the other supported target, Linux, only has one way to observe input (`evdev`),
+/// so there's nothing to select between.
+#[cfg(windows)]
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum Backend {
+    /// `WH_KEYBOARD_LL`/`WH_MOUSE_LL` hooks, installed via `SetWindowsHookExW`
+    ///
+    /// Requires a message loop on the installing thread and is globally serialized with every
+    /// other low-level hook in the system.
+    #[default]
+    Hook,
+    /// `RegisterRawInputDevices`, delivering input to a hidden message-only window
+    ///
+    /// Avoids the system-wide hook chain and gives raw per-device deltas, at the cost of only
+    /// reporting [`crate::KeyboardEvent`]/[`crate::MouseEvent`] (the raw `callback` path has no
+    /// equivalent `WPARAM`/`LPARAM` pair under this backend).
+    ///
+    /// Unlike [`Backend::Hook`], this backend has no way to suppress an event from reaching the
+    /// rest of the system — [`crate::Propagation::Block`] returned from a callback is silently
+    /// ignored.
+    RawInput,
+}
+
 #[derive(Clone)]
 pub struct HidCallback(pub Arc<Mutex<dyn Call + Send>>);
 
-/// Wrapper type which couples a raw hook and its associated global callbacks
+/// Wrapper type which couples a backend's active state and its associated global callbacks
+///
+/// The backend-specific [`HookHandle`] is returned by installing it and held by the caller.
 #[derive(Default)]
 struct Hook {
-    raw: HHOOK,
+    active: bool,
     callbacks: Vec<GlobalCallback>,
 }
 
 impl Hook {
-    /// Hook must be **valid** before calling
+    /// Hook must be **active** before calling
     fn register_global_callback(&mut self, hid_type: HidType, hid_callback: HidCallback) {
-        assert!(!self.raw.is_invalid());
+        assert!(self.active);
         self.callbacks
             .push(GlobalCallback::new(hid_type, hid_callback));
     }
-    /// Hook must be **invalid** before calling
-    fn hook(&mut self, hid_type: HidType, hid_callbacks: &Vec<HidCallback>) -> Result<()> {
-        assert!(self.raw.is_invalid());
-        self.raw = windows::hook(hid_type)?;
+
+    /// Hook must be **inactive** before calling. Installs a `WH_KEYBOARD_LL`/`WH_MOUSE_LL` hook.
+    #[cfg(windows)]
+    fn hook(&mut self, hid_type: HidType, hid_callbacks: &Vec<HidCallback>) -> Result<HookHandle> {
+        assert!(!self.active);
+        let raw = windows::hook(hid_type)?;
+        self.active = true;
         for hid_callback in hid_callbacks {
             // Enable "local" HID callbacks by inserting them into the global callback registry
             self.register_global_callback(hid_type, hid_callback.clone());
         }
+        Ok(HookHandle::Hook(raw))
+    }
+
+    /// Hook must be **inactive** before calling. Starts (or joins) the shared `evdev` reader
+    /// thread.
+    #[cfg(unix)]
+    fn hook(&mut self, hid_type: HidType, hid_callbacks: &Vec<HidCallback>) -> Result<HookHandle> {
+        assert!(!self.active);
+        unix::start()?;
+        self.active = true;
+        for hid_callback in hid_callbacks {
+            self.register_global_callback(hid_type, hid_callback.clone());
+        }
+        Ok(HookHandle::Evdev)
+    }
+
+    /// Hook must be **inactive** before calling. Marks the hook active without installing a
+    /// `WH_KEYBOARD_LL`/`WH_MOUSE_LL` hook, for backends (e.g. [`Backend::RawInput`]) that
+    /// deliver events some other way.
+    #[cfg(windows)]
+    fn activate_without_hook(&mut self, hid_type: HidType, hid_callbacks: &Vec<HidCallback>) {
+        assert!(!self.active);
+        self.active = true;
+        for hid_callback in hid_callbacks {
+            self.register_global_callback(hid_type, hid_callback.clone());
+        }
+    }
+
+    /// Hook must be **active** before calling
+    #[cfg(windows)]
+    fn unhook(&mut self, handle: HookHandle) -> Result<()> {
+        assert!(self.active);
+        if let HookHandle::Hook(raw) = handle {
+            windows::unhook(raw)?;
+        }
+        self.active = false;
+        // Clear global callbacks, effectively disabling them
+        self.callbacks.clear();
         Ok(())
     }
-    /// Hook must be **valid** before calling
-    fn unhook(&mut self) -> Result<()> {
-        assert!(!self.raw.is_invalid());
-        windows::unhook(self.raw)?;
-        self.raw = HHOOK::default();
+
+    /// Hook must be **active** before calling
+    #[cfg(unix)]
+    fn unhook(&mut self, _handle: HookHandle) -> Result<()> {
+        assert!(self.active);
+        unix::stop();
+        self.active = false;
         // Clear global callbacks, effectively disabling them
         self.callbacks.clear();
         Ok(())
     }
+
     fn valid(&self) -> bool {
-        !self.raw.is_invalid()
+        self.active
     }
+
     fn clear_global_callbacks(&mut self) {
         self.callbacks.clear();
     }
 }
 
+/// The resource returned by installing a [`Hook`], passed back in to tear it down
+enum HookHandle {
+    #[cfg(windows)]
+    Hook(HHOOK),
+    #[cfg(windows)]
+    RawInput,
+    #[cfg(unix)]
+    Evdev,
+}
+
+/// The background thread started by [`HidMonitor::spawn`]
+#[cfg(windows)]
+struct SpawnedThread {
+    thread_id: u32,
+    handle: JoinHandle<()>,
+}
+
 /// Callback-based HID event monitoring
 pub struct HidMonitor {
+    #[cfg(windows)]
+    backend: Backend,
     keybd_hook: Hook,
     mouse_hook: Hook,
+    keybd_hook_handle: Option<HookHandle>,
+    mouse_hook_handle: Option<HookHandle>,
+    #[cfg(windows)]
+    raw_input_window: Option<HWND>,
     keybd_callbacks: Vec<HidCallback>,
     mouse_callbacks: Vec<HidCallback>,
+    hotkey_dispatcher: Arc<Mutex<HotkeyDispatcher>>,
+    hotkey_dispatcher_installed: bool,
+    #[cfg(windows)]
+    spawned: Option<SpawnedThread>,
 }
 
 impl Default for HidMonitor {
     /// Creates a new `HidMonitor` with all callbacks disabled
+    ///
+    /// On Windows, uses the [`Backend::Hook`] backend.
     fn default() -> Self {
+        #[cfg(windows)]
+        return Self::new(Backend::default());
+        #[cfg(unix)]
+        Self::new()
+    }
+}
+
+impl HidMonitor {
+    /// Creates a new `HidMonitor` using the given `backend`, with all callbacks disabled
+    #[cfg(windows)]
+    pub fn new(backend: Backend) -> Self {
         Self {
+            backend,
             keybd_hook: Hook::default(),
             mouse_hook: Hook::default(),
+            keybd_hook_handle: None,
+            mouse_hook_handle: None,
+            raw_input_window: None,
             keybd_callbacks: Vec::default(),
             mouse_callbacks: Vec::default(),
+            hotkey_dispatcher: Arc::default(),
+            hotkey_dispatcher_installed: false,
+            spawned: None,
         }
     }
-}
 
-impl HidMonitor {
+    /// Creates a new `HidMonitor`, with all callbacks disabled
+    #[cfg(unix)]
+    pub fn new() -> Self {
+        Self {
+            keybd_hook: Hook::default(),
+            mouse_hook: Hook::default(),
+            keybd_hook_handle: None,
+            mouse_hook_handle: None,
+            keybd_callbacks: Vec::default(),
+            mouse_callbacks: Vec::default(),
+            hotkey_dispatcher: Arc::default(),
+            hotkey_dispatcher_installed: false,
+        }
+    }
+
+    /// Ensures the raw-input message window exists and is registered for both device classes;
+    /// only does any work the first time it's called for this `HidMonitor`
+    #[cfg(windows)]
+    fn ensure_raw_input_window(&mut self) -> Result<()> {
+        if self.raw_input_window.is_some() {
+            return Ok(());
+        }
+        let hwnd = raw_input::create_message_window()?;
+        raw_input::register_devices(hwnd)?;
+        self.raw_input_window = Some(hwnd);
+        Ok(())
+    }
+
+    /// Tears down the raw-input message window once neither hook is active anymore
+    #[cfg(windows)]
+    fn maybe_teardown_raw_input_window(&mut self) -> Result<()> {
+        if self.keybd_hook.valid() || self.mouse_hook.valid() {
+            return Ok(());
+        }
+        if let Some(hwnd) = self.raw_input_window.take() {
+            raw_input::destroy_message_window(hwnd)?;
+        }
+        Ok(())
+    }
+
     /// Enables HID callbacks
     ///
     /// ## ⚠️ Warning
     ///
-    /// * Windows targets
+    /// * Windows targets, [`Backend::Hook`]
     ///     * You ***MUST*** have a [message loop](https://learn.microsoft.com/en-us/windows/win32/winmsg/using-messages-and-message-queues#creating-a-message-loop)
     ///       running on the same thread as the `HidMonitor` hooks enabled by this function, otherwise your system may become
     ///       unresponsive!  For maximum safety, ensure the message loop is running **before** enabling the HID monitor, or shortly after.
@@ -90,38 +264,293 @@ impl HidMonitor {
     ///     * Read more about the implications of this function on the `WinApi` documentation for
     ///       [`SetWindowsHookExA`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowshookexa#remarks)
     ///
+    /// `enable`/`disable` and [`HidMonitor::spawn`]/[`HidMonitor::stop`] are mutually exclusive:
+    /// once `spawn` has installed hooks on its own background thread, `enable`/`disable` return
+    /// [`Error::Spawned`] until [`HidMonitor::stop`] is called.
+    ///
     /// ## Errors
     ///
     /// Windows: [`SetWindowsHookExA`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowshookexa)
-    /// returned an error
+    /// (or, under [`Backend::RawInput`], `RegisterRawInputDevices`) returned an error, or this
+    /// `HidMonitor` is currently [`HidMonitor::spawn`]ed ([`Error::Spawned`])
+    #[cfg(windows)]
     pub fn enable(&mut self, hid_type: HidType) -> Result<&mut Self> {
+        if self.spawned.is_some() {
+            return Err(Error::Spawned);
+        }
+        let already_valid = match hid_type {
+            HidType::Keyboard => self.keybd_hook.valid(),
+            HidType::Mouse => self.mouse_hook.valid(),
+        };
+        if already_valid {
+            return Ok(self);
+        }
+        if matches!(self.backend, Backend::RawInput) {
+            self.ensure_raw_input_window()?;
+        }
+        let backend = self.backend;
         let (hook, callbacks) = match hid_type {
             HidType::Keyboard => (&mut self.keybd_hook, &self.keybd_callbacks),
             HidType::Mouse => (&mut self.mouse_hook, &self.mouse_callbacks),
         };
-        if !hook.valid() {
-            hook.hook(hid_type, callbacks)?;
+        let handle = match backend {
+            Backend::Hook => hook.hook(hid_type, callbacks)?,
+            Backend::RawInput => {
+                hook.activate_without_hook(hid_type, callbacks);
+                HookHandle::RawInput
+            }
+        };
+        match hid_type {
+            HidType::Keyboard => self.keybd_hook_handle = Some(handle),
+            HidType::Mouse => self.mouse_hook_handle = Some(handle),
+        }
+        Ok(self)
+    }
+
+    /// Enables HID callbacks, starting the shared `evdev` reader thread the first time either
+    /// `HidType` is enabled
+    ///
+    /// ## Errors
+    ///
+    /// Failure to open or read `/dev/input/event*` devices
+    #[cfg(unix)]
+    pub fn enable(&mut self, hid_type: HidType) -> Result<&mut Self> {
+        let (hook, handle_slot, callbacks) = match hid_type {
+            HidType::Keyboard => (
+                &mut self.keybd_hook,
+                &mut self.keybd_hook_handle,
+                &self.keybd_callbacks,
+            ),
+            HidType::Mouse => (
+                &mut self.mouse_hook,
+                &mut self.mouse_hook_handle,
+                &self.mouse_callbacks,
+            ),
+        };
+        if hook.valid() {
+            return Ok(self);
         }
+        *handle_slot = Some(hook.hook(hid_type, callbacks)?);
         Ok(self)
     }
 
     /// Disables HID callbacks
     ///
+    /// `enable`/`disable` and [`HidMonitor::spawn`]/[`HidMonitor::stop`] are mutually exclusive;
+    /// see [`HidMonitor::enable`].
+    ///
     /// ## Errors
     ///
     /// Windows: [`UnhookWindowsHookEx`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-unhookwindowshookex)
-    /// returned an error
+    /// returned an error, or this `HidMonitor` is currently [`HidMonitor::spawn`]ed
+    /// ([`Error::Spawned`])
+    #[cfg(windows)]
     pub fn disable(&mut self, hid_type: HidType) -> Result<&mut Self> {
-        let hook = match hid_type {
-            HidType::Keyboard => &mut self.keybd_hook,
-            HidType::Mouse => &mut self.mouse_hook,
+        if self.spawned.is_some() {
+            return Err(Error::Spawned);
+        }
+        let (hook, handle) = match hid_type {
+            HidType::Keyboard => (&mut self.keybd_hook, &mut self.keybd_hook_handle),
+            HidType::Mouse => (&mut self.mouse_hook, &mut self.mouse_hook_handle),
         };
         if hook.valid() {
-            hook.unhook()?;
+            let handle = handle.take().unwrap_or(HookHandle::RawInput);
+            hook.unhook(handle)?;
+        }
+        if matches!(self.backend, Backend::RawInput) {
+            self.maybe_teardown_raw_input_window()?;
+        }
+        if matches!(hid_type, HidType::Keyboard) {
+            // Stuck modifiers can't survive a disable/re-enable cycle
+            self.hotkey_dispatcher.lock().unwrap().reset();
+        }
+        Ok(self)
+    }
+
+    /// Disables HID callbacks, stopping the shared `evdev` reader thread once neither `HidType`
+    /// is enabled anymore
+    #[cfg(unix)]
+    pub fn disable(&mut self, hid_type: HidType) -> Result<&mut Self> {
+        let (hook, handle) = match hid_type {
+            HidType::Keyboard => (&mut self.keybd_hook, &mut self.keybd_hook_handle),
+            HidType::Mouse => (&mut self.mouse_hook, &mut self.mouse_hook_handle),
+        };
+        if hook.valid() {
+            let handle = handle.take().unwrap_or(HookHandle::Evdev);
+            hook.unhook(handle)?;
+        }
+        if matches!(hid_type, HidType::Keyboard) {
+            // Stuck modifiers can't survive a disable/re-enable cycle
+            self.hotkey_dispatcher.lock().unwrap().reset();
+        }
+        Ok(self)
+    }
+
+    /// Runs the currently-registered HID hooks on a dedicated background thread, owned by this
+    /// `HidMonitor`
+    ///
+    /// `SetWindowsHookExW` requires a message loop running on the same thread that installed the
+    /// hook; `spawn` takes care of both, installing the hooks and pumping messages on an internal
+    /// thread so the caller doesn't have to run [`crate::message_loop`] itself. Call
+    /// [`HidMonitor::stop`] (or drop the `HidMonitor`) to unhook and join the thread.
+    ///
+    /// Calling `spawn` again while already spawned is a no-op; callbacks added afterward via
+    /// [`HidMonitor::add_callback`] are not picked up until the next `spawn`.
+    ///
+    /// ## Errors
+    ///
+    /// Windows: [`SetWindowsHookExA`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-setwindowshookexa)
+    /// (or, under [`Backend::RawInput`], `RegisterRawInputDevices`) returned an error on the
+    /// background thread; the error is reported back to this call, and nothing is left installed
+    #[cfg(windows)]
+    pub fn spawn(&mut self) -> Result<&mut Self> {
+        if self.spawned.is_some() {
+            return Ok(self);
         }
+        let backend = self.backend;
+        let keybd_callbacks = self.keybd_callbacks.clone();
+        let mouse_callbacks = self.mouse_callbacks.clone();
+        let (result_tx, result_rx) = mpsc::channel::<Result<u32>>();
+        let handle = std::thread::spawn(move || {
+            let mut keybd_hook = Hook::default();
+            let mut mouse_hook = Hook::default();
+            let mut keybd_handle: Option<HookHandle> = None;
+            let mut mouse_handle: Option<HookHandle> = None;
+            let mut raw_input_hwnd: Option<HWND> = None;
+
+            let setup: Result<()> = (|| match backend {
+                Backend::Hook => {
+                    keybd_handle = Some(keybd_hook.hook(HidType::Keyboard, &keybd_callbacks)?);
+                    mouse_handle = Some(mouse_hook.hook(HidType::Mouse, &mouse_callbacks)?);
+                    Ok(())
+                }
+                Backend::RawInput => {
+                    let hwnd = raw_input::create_message_window()?;
+                    raw_input::register_devices(hwnd)?;
+                    raw_input_hwnd = Some(hwnd);
+                    keybd_hook.activate_without_hook(HidType::Keyboard, &keybd_callbacks);
+                    mouse_hook.activate_without_hook(HidType::Mouse, &mouse_callbacks);
+                    keybd_handle = Some(HookHandle::RawInput);
+                    mouse_handle = Some(HookHandle::RawInput);
+                    Ok(())
+                }
+            })();
+
+            match setup {
+                Ok(()) => {
+                    let _ = result_tx.send(Ok(windows::current_thread_id()));
+                    windows::message_loop();
+                }
+                Err(err) => {
+                    let _ = result_tx.send(Err(err));
+                }
+            }
+
+            // Tear down whatever was successfully installed, whether `spawn` failed partway or
+            // the message loop above returned normally after `HidMonitor::stop`
+            if let Some(handle) = keybd_handle.take() {
+                let _ = keybd_hook.unhook(handle);
+            }
+            if let Some(handle) = mouse_handle.take() {
+                let _ = mouse_hook.unhook(handle);
+            }
+            if let Some(hwnd) = raw_input_hwnd.take() {
+                let _ = raw_input::destroy_message_window(hwnd);
+            }
+        });
+        let result = result_rx
+            .recv()
+            .expect("spawned HID thread exited before reporting its setup result");
+        match result {
+            Ok(thread_id) => {
+                self.spawned = Some(SpawnedThread { thread_id, handle });
+                Ok(self)
+            }
+            Err(err) => {
+                // The thread has already torn itself down (or never installed anything); just
+                // join it rather than leaving it detached
+                let _ = handle.join();
+                Err(err)
+            }
+        }
+    }
+
+    /// Enables both `HidType`s, each already backed by the background `evdev` reader thread
+    /// started by [`HidMonitor::enable`]
+    ///
+    /// Linux's `evdev` backend has no equivalent to Windows' "install hook + pump messages on the
+    /// same thread" requirement, so unlike the Windows implementation, `spawn` doesn't need to own
+    /// a dedicated thread itself; it's provided so the same calling code works on both platforms.
+    ///
+    /// ## Errors
+    ///
+    /// Failure to open or read `/dev/input/event*` devices
+    #[cfg(unix)]
+    pub fn spawn(&mut self) -> Result<&mut Self> {
+        self.enable(HidType::Keyboard)?;
+        self.enable(HidType::Mouse)?;
         Ok(self)
     }
 
+    /// Stops the background thread started by [`HidMonitor::spawn`], unhooking and joining it
+    ///
+    /// Does nothing if `spawn` was never called, or has already been stopped.
+    ///
+    /// ## Errors
+    ///
+    /// Windows: [`PostThreadMessageW`](https://learn.microsoft.com/en-us/windows/win32/api/winuser/nf-winuser-postthreadmessagew)
+    /// returned an error
+    #[cfg(windows)]
+    pub fn stop(&mut self) -> Result<&mut Self> {
+        if let Some(spawned) = self.spawned.take() {
+            windows::post_quit(spawned.thread_id)?;
+            let _ = spawned.handle.join();
+            // Stuck modifiers can't survive a disable/re-enable cycle, same as `disable`
+            self.hotkey_dispatcher.lock().unwrap().reset();
+        }
+        Ok(self)
+    }
+
+    /// Disables both `HidType`s enabled by [`HidMonitor::spawn`]
+    #[cfg(unix)]
+    pub fn stop(&mut self) -> Result<&mut Self> {
+        self.disable(HidType::Keyboard)?;
+        self.disable(HidType::Mouse)?;
+        Ok(self)
+    }
+
+    /// Registers a callback that fires when `key` is pressed while exactly `modifiers` are held
+    ///
+    /// Only fires on the trigger key's initial key-down; auto-repeat key-downs while it's held
+    /// are ignored. Requires the keyboard hook to be [`HidMonitor::enable`]d to observe events.
+    ///
+    /// ## ⚠️ Warning
+    ///
+    /// `callback` runs synchronously on the thread delivering the keyboard event, with the
+    /// hotkey dispatcher's own lock held; see the warning on [`crate::Call`]. In particular,
+    /// `callback` must not call [`HidMonitor::register_hotkey`]/[`HidMonitor::unregister_hotkey`]
+    /// (including unregistering itself) or it will deadlock that thread.
+    pub fn register_hotkey(
+        &mut self,
+        key: KeyCode,
+        modifiers: Modifiers,
+        callback: impl FnMut() + Send + 'static,
+    ) -> HotkeyId {
+        if !self.hotkey_dispatcher_installed {
+            self.add_callback(HidType::Keyboard, HidCallback(self.hotkey_dispatcher.clone()));
+            self.hotkey_dispatcher_installed = true;
+        }
+        self.hotkey_dispatcher
+            .lock()
+            .unwrap()
+            .register(key, modifiers, Box::new(callback))
+    }
+
+    /// Unregisters a hotkey previously returned by [`HidMonitor::register_hotkey`]
+    pub fn unregister_hotkey(&mut self, id: HotkeyId) {
+        self.hotkey_dispatcher.lock().unwrap().unregister(id);
+    }
+
     /// Adds a new HID callback
     ///
     /// Any number of callbacks may be added for a given `HidType`, but callback order is non-deterministic.
@@ -154,5 +583,6 @@ impl Drop for HidMonitor {
     fn drop(&mut self) {
         let _ = self.disable(HidType::Keyboard);
         let _ = self.disable(HidType::Mouse);
+        let _ = self.stop();
     }
 }
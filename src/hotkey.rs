@@ -0,0 +1,157 @@
+use std::collections::{HashMap, HashSet};
+
+#[cfg(windows)]
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+
+use crate::events::KeyboardEvent;
+use crate::key_code::KeyCode;
+use crate::traits::{Call, Propagation};
+
+/// The modifier keys held alongside a [`Hotkey`]'s trigger key
+///
+/// Left and right variants of a modifier (e.g. `LeftControl`/`RightControl`) are treated
+/// interchangeably; a `Hotkey` does not distinguish which side was pressed.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub shift: bool,
+    pub alt: bool,
+    pub win: bool,
+}
+
+impl Modifiers {
+    pub const NONE: Self = Self {
+        ctrl: false,
+        shift: false,
+        alt: false,
+        win: false,
+    };
+
+    fn matches(&self, held: &HashSet<KeyCode>) -> bool {
+        let held_ctrl =
+            held.contains(&KeyCode::LeftControl) || held.contains(&KeyCode::RightControl);
+        let held_shift = held.contains(&KeyCode::LeftShift) || held.contains(&KeyCode::RightShift);
+        let held_alt = held.contains(&KeyCode::LeftAlt) || held.contains(&KeyCode::RightAlt);
+        let held_win = held.contains(&KeyCode::LeftWin) || held.contains(&KeyCode::RightWin);
+        self.ctrl == held_ctrl
+            && self.shift == held_shift
+            && self.alt == held_alt
+            && self.win == held_win
+    }
+}
+
+/// A registered key combination, e.g. `Ctrl+Shift+R`
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Hotkey {
+    pub key: KeyCode,
+    pub modifiers: Modifiers,
+}
+
+/// Identifies a registered [`Hotkey`] so it can later be passed to
+/// [`crate::HidMonitor::unregister_hotkey`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct HotkeyId(u64);
+
+fn is_modifier(key: KeyCode) -> bool {
+    matches!(
+        key,
+        KeyCode::LeftShift
+            | KeyCode::RightShift
+            | KeyCode::LeftControl
+            | KeyCode::RightControl
+            | KeyCode::LeftAlt
+            | KeyCode::RightAlt
+            | KeyCode::LeftWin
+            | KeyCode::RightWin
+    )
+}
+
+struct Registration {
+    hotkey: Hotkey,
+    callback: Box<dyn FnMut() + Send>,
+}
+
+/// Tracks modifier state and fires registered [`Hotkey`] callbacks from keyboard events
+///
+/// Installed internally as a regular keyboard callback the first time
+/// [`crate::HidMonitor::register_hotkey`] is called; not constructed directly.
+#[derive(Default)]
+pub(crate) struct HotkeyDispatcher {
+    held_modifiers: HashSet<KeyCode>,
+    down_triggers: HashSet<KeyCode>,
+    registrations: HashMap<u64, Registration>,
+    next_id: u64,
+}
+
+impl HotkeyDispatcher {
+    pub(crate) fn register(
+        &mut self,
+        key: KeyCode,
+        modifiers: Modifiers,
+        callback: Box<dyn FnMut() + Send>,
+    ) -> HotkeyId {
+        let id = self.next_id;
+        self.next_id += 1;
+        self.registrations.insert(
+            id,
+            Registration {
+                hotkey: Hotkey { key, modifiers },
+                callback,
+            },
+        );
+        HotkeyId(id)
+    }
+
+    pub(crate) fn unregister(&mut self, id: HotkeyId) {
+        self.registrations.remove(&id.0);
+    }
+
+    /// Clears tracked modifier/trigger state, called when the keyboard hook is disabled so a
+    /// stuck modifier can't survive across a disable/re-enable cycle
+    pub(crate) fn reset(&mut self) {
+        self.held_modifiers.clear();
+        self.down_triggers.clear();
+    }
+}
+
+impl Call for HotkeyDispatcher {
+    #[cfg(windows)]
+    fn callback(&mut self, _n_code: i32, _w_param: WPARAM, _l_param: LPARAM) -> Propagation {
+        Propagation::Pass
+    }
+
+    fn on_keyboard(&mut self, event: KeyboardEvent) -> Propagation {
+        match event {
+            KeyboardEvent::KeyDown { key, .. } if is_modifier(key) => {
+                self.held_modifiers.insert(key);
+            }
+            KeyboardEvent::KeyUp { key, .. } if is_modifier(key) => {
+                self.held_modifiers.remove(&key);
+            }
+            KeyboardEvent::KeyDown { key, .. } => {
+                // Debounce auto-repeat: only fire on the key's first transition to "down"
+                if !self.down_triggers.insert(key) {
+                    return Propagation::Pass;
+                }
+                for registration in self.registrations.values_mut() {
+                    let matches = registration.hotkey.key == key
+                        && registration
+                            .hotkey
+                            .modifiers
+                            .matches(&self.held_modifiers);
+                    if matches {
+                        // `self` is locked by the caller for the duration of this call (it's the
+                        // same `Arc<Mutex<HotkeyDispatcher>>` registered as a global callback);
+                        // see the warning on `HidMonitor::register_hotkey` — this must not call
+                        // back into `register_hotkey`/`unregister_hotkey` or it deadlocks.
+                        (registration.callback)();
+                    }
+                }
+            }
+            KeyboardEvent::KeyUp { key, .. } => {
+                self.down_triggers.remove(&key);
+            }
+        }
+        Propagation::Pass
+    }
+}
@@ -0,0 +1,500 @@
+use crate::Error;
+
+/// A platform-independent symbolic key, decoupled from any particular native virtual-key code
+///
+/// On Windows this maps to/from `VK_*` constants via [`From<u32>`] and [`TryFrom<KeyCode>`]. On
+/// Linux, [`KeyCode::from_linux_keycode`] maps from the kernel's `KEY_*` codes instead.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[non_exhaustive]
+pub enum KeyCode {
+    A,
+    B,
+    C,
+    D,
+    E,
+    F,
+    G,
+    H,
+    I,
+    J,
+    K,
+    L,
+    M,
+    N,
+    O,
+    P,
+    Q,
+    R,
+    S,
+    T,
+    U,
+    V,
+    W,
+    X,
+    Y,
+    Z,
+    Digit0,
+    Digit1,
+    Digit2,
+    Digit3,
+    Digit4,
+    Digit5,
+    Digit6,
+    Digit7,
+    Digit8,
+    Digit9,
+    F1,
+    F2,
+    F3,
+    F4,
+    F5,
+    F6,
+    F7,
+    F8,
+    F9,
+    F10,
+    F11,
+    F12,
+    F13,
+    F14,
+    F15,
+    F16,
+    F17,
+    F18,
+    F19,
+    F20,
+    F21,
+    F22,
+    F23,
+    F24,
+    ArrowUp,
+    ArrowDown,
+    ArrowLeft,
+    ArrowRight,
+    LeftShift,
+    RightShift,
+    LeftControl,
+    RightControl,
+    LeftAlt,
+    RightAlt,
+    LeftWin,
+    RightWin,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    Numpad0,
+    Numpad1,
+    Numpad2,
+    Numpad3,
+    Numpad4,
+    Numpad5,
+    Numpad6,
+    Numpad7,
+    Numpad8,
+    Numpad9,
+    NumpadAdd,
+    NumpadSubtract,
+    NumpadMultiply,
+    NumpadDivide,
+    NumpadDecimal,
+    NumpadSeparator,
+    Comma,
+    Minus,
+    Period,
+    Equals,
+    Semicolon,
+    Slash,
+    Backslash,
+    Quote,
+    Grave,
+    LeftBracket,
+    RightBracket,
+    Space,
+    Tab,
+    Enter,
+    Escape,
+    Backspace,
+    Insert,
+    Delete,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    /// A native key code this crate does not yet map to a named variant
+    Unrecognized,
+}
+
+impl From<u32> for KeyCode {
+    /// Maps a Windows virtual-key code to a [`KeyCode`]
+    ///
+    /// Codes this crate does not recognize fall back to [`KeyCode::Unrecognized`], which is why
+    /// the reverse conversion, `TryFrom<KeyCode> for u32`, is fallible.
+    fn from(vk_code: u32) -> Self {
+        match vk_code {
+            0x08 => KeyCode::Backspace,
+            0x09 => KeyCode::Tab,
+            0x0D => KeyCode::Enter,
+            0x1B => KeyCode::Escape,
+            0x20 => KeyCode::Space,
+            0x21 => KeyCode::PageUp,
+            0x22 => KeyCode::PageDown,
+            0x23 => KeyCode::End,
+            0x24 => KeyCode::Home,
+            0x25 => KeyCode::ArrowLeft,
+            0x26 => KeyCode::ArrowUp,
+            0x27 => KeyCode::ArrowRight,
+            0x28 => KeyCode::ArrowDown,
+            0x2D => KeyCode::Insert,
+            0x2E => KeyCode::Delete,
+            0x30 => KeyCode::Digit0,
+            0x31 => KeyCode::Digit1,
+            0x32 => KeyCode::Digit2,
+            0x33 => KeyCode::Digit3,
+            0x34 => KeyCode::Digit4,
+            0x35 => KeyCode::Digit5,
+            0x36 => KeyCode::Digit6,
+            0x37 => KeyCode::Digit7,
+            0x38 => KeyCode::Digit8,
+            0x39 => KeyCode::Digit9,
+            0x41 => KeyCode::A,
+            0x42 => KeyCode::B,
+            0x43 => KeyCode::C,
+            0x44 => KeyCode::D,
+            0x45 => KeyCode::E,
+            0x46 => KeyCode::F,
+            0x47 => KeyCode::G,
+            0x48 => KeyCode::H,
+            0x49 => KeyCode::I,
+            0x4A => KeyCode::J,
+            0x4B => KeyCode::K,
+            0x4C => KeyCode::L,
+            0x4D => KeyCode::M,
+            0x4E => KeyCode::N,
+            0x4F => KeyCode::O,
+            0x50 => KeyCode::P,
+            0x51 => KeyCode::Q,
+            0x52 => KeyCode::R,
+            0x53 => KeyCode::S,
+            0x54 => KeyCode::T,
+            0x55 => KeyCode::U,
+            0x56 => KeyCode::V,
+            0x57 => KeyCode::W,
+            0x58 => KeyCode::X,
+            0x59 => KeyCode::Y,
+            0x5A => KeyCode::Z,
+            0x5B => KeyCode::LeftWin,
+            0x5C => KeyCode::RightWin,
+            0x60 => KeyCode::Numpad0,
+            0x61 => KeyCode::Numpad1,
+            0x62 => KeyCode::Numpad2,
+            0x63 => KeyCode::Numpad3,
+            0x64 => KeyCode::Numpad4,
+            0x65 => KeyCode::Numpad5,
+            0x66 => KeyCode::Numpad6,
+            0x67 => KeyCode::Numpad7,
+            0x68 => KeyCode::Numpad8,
+            0x69 => KeyCode::Numpad9,
+            0x6A => KeyCode::NumpadMultiply,
+            0x6B => KeyCode::NumpadAdd,
+            0x6C => KeyCode::NumpadSeparator,
+            0x6D => KeyCode::NumpadSubtract,
+            0x6E => KeyCode::NumpadDecimal,
+            0x6F => KeyCode::NumpadDivide,
+            0x70 => KeyCode::F1,
+            0x71 => KeyCode::F2,
+            0x72 => KeyCode::F3,
+            0x73 => KeyCode::F4,
+            0x74 => KeyCode::F5,
+            0x75 => KeyCode::F6,
+            0x76 => KeyCode::F7,
+            0x77 => KeyCode::F8,
+            0x78 => KeyCode::F9,
+            0x79 => KeyCode::F10,
+            0x7A => KeyCode::F11,
+            0x7B => KeyCode::F12,
+            0x7C => KeyCode::F13,
+            0x7D => KeyCode::F14,
+            0x7E => KeyCode::F15,
+            0x7F => KeyCode::F16,
+            0x80 => KeyCode::F17,
+            0x81 => KeyCode::F18,
+            0x82 => KeyCode::F19,
+            0x83 => KeyCode::F20,
+            0x84 => KeyCode::F21,
+            0x85 => KeyCode::F22,
+            0x86 => KeyCode::F23,
+            0x87 => KeyCode::F24,
+            0x90 => KeyCode::NumLock,
+            0x91 => KeyCode::ScrollLock,
+            0xA0 => KeyCode::LeftShift,
+            0xA1 => KeyCode::RightShift,
+            0xA2 => KeyCode::LeftControl,
+            0xA3 => KeyCode::RightControl,
+            0xA4 => KeyCode::LeftAlt,
+            0xA5 => KeyCode::RightAlt,
+            0xBA => KeyCode::Semicolon,
+            0xBB => KeyCode::Equals,
+            0xBC => KeyCode::Comma,
+            0xBD => KeyCode::Minus,
+            0xBE => KeyCode::Period,
+            0xBF => KeyCode::Slash,
+            0xC0 => KeyCode::Grave,
+            0xDB => KeyCode::LeftBracket,
+            0xDC => KeyCode::Backslash,
+            0xDD => KeyCode::RightBracket,
+            0xDE => KeyCode::Quote,
+            0x14 => KeyCode::CapsLock,
+            _ => KeyCode::Unrecognized,
+        }
+    }
+}
+
+impl TryFrom<KeyCode> for u32 {
+    type Error = Error;
+
+    /// Maps a [`KeyCode`] back to its Windows virtual-key code
+    ///
+    /// Fails for [`KeyCode::Unrecognized`], which does not carry the originating code.
+    fn try_from(key_code: KeyCode) -> Result<Self, Self::Error> {
+        Ok(match key_code {
+            KeyCode::Backspace => 0x08,
+            KeyCode::Tab => 0x09,
+            KeyCode::Enter => 0x0D,
+            KeyCode::Escape => 0x1B,
+            KeyCode::Space => 0x20,
+            KeyCode::PageUp => 0x21,
+            KeyCode::PageDown => 0x22,
+            KeyCode::End => 0x23,
+            KeyCode::Home => 0x24,
+            KeyCode::ArrowLeft => 0x25,
+            KeyCode::ArrowUp => 0x26,
+            KeyCode::ArrowRight => 0x27,
+            KeyCode::ArrowDown => 0x28,
+            KeyCode::Insert => 0x2D,
+            KeyCode::Delete => 0x2E,
+            KeyCode::Digit0 => 0x30,
+            KeyCode::Digit1 => 0x31,
+            KeyCode::Digit2 => 0x32,
+            KeyCode::Digit3 => 0x33,
+            KeyCode::Digit4 => 0x34,
+            KeyCode::Digit5 => 0x35,
+            KeyCode::Digit6 => 0x36,
+            KeyCode::Digit7 => 0x37,
+            KeyCode::Digit8 => 0x38,
+            KeyCode::Digit9 => 0x39,
+            KeyCode::A => 0x41,
+            KeyCode::B => 0x42,
+            KeyCode::C => 0x43,
+            KeyCode::D => 0x44,
+            KeyCode::E => 0x45,
+            KeyCode::F => 0x46,
+            KeyCode::G => 0x47,
+            KeyCode::H => 0x48,
+            KeyCode::I => 0x49,
+            KeyCode::J => 0x4A,
+            KeyCode::K => 0x4B,
+            KeyCode::L => 0x4C,
+            KeyCode::M => 0x4D,
+            KeyCode::N => 0x4E,
+            KeyCode::O => 0x4F,
+            KeyCode::P => 0x50,
+            KeyCode::Q => 0x51,
+            KeyCode::R => 0x52,
+            KeyCode::S => 0x53,
+            KeyCode::T => 0x54,
+            KeyCode::U => 0x55,
+            KeyCode::V => 0x56,
+            KeyCode::W => 0x57,
+            KeyCode::X => 0x58,
+            KeyCode::Y => 0x59,
+            KeyCode::Z => 0x5A,
+            KeyCode::LeftWin => 0x5B,
+            KeyCode::RightWin => 0x5C,
+            KeyCode::Numpad0 => 0x60,
+            KeyCode::Numpad1 => 0x61,
+            KeyCode::Numpad2 => 0x62,
+            KeyCode::Numpad3 => 0x63,
+            KeyCode::Numpad4 => 0x64,
+            KeyCode::Numpad5 => 0x65,
+            KeyCode::Numpad6 => 0x66,
+            KeyCode::Numpad7 => 0x67,
+            KeyCode::Numpad8 => 0x68,
+            KeyCode::Numpad9 => 0x69,
+            KeyCode::NumpadMultiply => 0x6A,
+            KeyCode::NumpadAdd => 0x6B,
+            KeyCode::NumpadSeparator => 0x6C,
+            KeyCode::NumpadSubtract => 0x6D,
+            KeyCode::NumpadDecimal => 0x6E,
+            KeyCode::NumpadDivide => 0x6F,
+            KeyCode::F1 => 0x70,
+            KeyCode::F2 => 0x71,
+            KeyCode::F3 => 0x72,
+            KeyCode::F4 => 0x73,
+            KeyCode::F5 => 0x74,
+            KeyCode::F6 => 0x75,
+            KeyCode::F7 => 0x76,
+            KeyCode::F8 => 0x77,
+            KeyCode::F9 => 0x78,
+            KeyCode::F10 => 0x79,
+            KeyCode::F11 => 0x7A,
+            KeyCode::F12 => 0x7B,
+            KeyCode::F13 => 0x7C,
+            KeyCode::F14 => 0x7D,
+            KeyCode::F15 => 0x7E,
+            KeyCode::F16 => 0x7F,
+            KeyCode::F17 => 0x80,
+            KeyCode::F18 => 0x81,
+            KeyCode::F19 => 0x82,
+            KeyCode::F20 => 0x83,
+            KeyCode::F21 => 0x84,
+            KeyCode::F22 => 0x85,
+            KeyCode::F23 => 0x86,
+            KeyCode::F24 => 0x87,
+            KeyCode::NumLock => 0x90,
+            KeyCode::ScrollLock => 0x91,
+            KeyCode::LeftShift => 0xA0,
+            KeyCode::RightShift => 0xA1,
+            KeyCode::LeftControl => 0xA2,
+            KeyCode::RightControl => 0xA3,
+            KeyCode::LeftAlt => 0xA4,
+            KeyCode::RightAlt => 0xA5,
+            KeyCode::Semicolon => 0xBA,
+            KeyCode::Equals => 0xBB,
+            KeyCode::Comma => 0xBC,
+            KeyCode::Minus => 0xBD,
+            KeyCode::Period => 0xBE,
+            KeyCode::Slash => 0xBF,
+            KeyCode::Grave => 0xC0,
+            KeyCode::LeftBracket => 0xDB,
+            KeyCode::Backslash => 0xDC,
+            KeyCode::RightBracket => 0xDD,
+            KeyCode::Quote => 0xDE,
+            KeyCode::CapsLock => 0x14,
+            KeyCode::Unrecognized => return Err(Error::NoNativeKeyCode),
+        })
+    }
+}
+
+#[cfg(unix)]
+impl KeyCode {
+    /// Maps a Linux kernel `KEY_*` code (from `linux/input-event-codes.h`), as reported by
+    /// `evdev`, to a [`KeyCode`]
+    pub fn from_linux_keycode(code: u16) -> Self {
+        match code {
+            1 => KeyCode::Escape,
+            2 => KeyCode::Digit1,
+            3 => KeyCode::Digit2,
+            4 => KeyCode::Digit3,
+            5 => KeyCode::Digit4,
+            6 => KeyCode::Digit5,
+            7 => KeyCode::Digit6,
+            8 => KeyCode::Digit7,
+            9 => KeyCode::Digit8,
+            10 => KeyCode::Digit9,
+            11 => KeyCode::Digit0,
+            12 => KeyCode::Minus,
+            13 => KeyCode::Equals,
+            14 => KeyCode::Backspace,
+            15 => KeyCode::Tab,
+            16 => KeyCode::Q,
+            17 => KeyCode::W,
+            18 => KeyCode::E,
+            19 => KeyCode::R,
+            20 => KeyCode::T,
+            21 => KeyCode::Y,
+            22 => KeyCode::U,
+            23 => KeyCode::I,
+            24 => KeyCode::O,
+            25 => KeyCode::P,
+            26 => KeyCode::LeftBracket,
+            27 => KeyCode::RightBracket,
+            28 => KeyCode::Enter,
+            29 => KeyCode::LeftControl,
+            30 => KeyCode::A,
+            31 => KeyCode::S,
+            32 => KeyCode::D,
+            33 => KeyCode::F,
+            34 => KeyCode::G,
+            35 => KeyCode::H,
+            36 => KeyCode::J,
+            37 => KeyCode::K,
+            38 => KeyCode::L,
+            39 => KeyCode::Semicolon,
+            40 => KeyCode::Quote,
+            41 => KeyCode::Grave,
+            42 => KeyCode::LeftShift,
+            43 => KeyCode::Backslash,
+            44 => KeyCode::Z,
+            45 => KeyCode::X,
+            46 => KeyCode::C,
+            47 => KeyCode::V,
+            48 => KeyCode::B,
+            49 => KeyCode::N,
+            50 => KeyCode::M,
+            51 => KeyCode::Comma,
+            52 => KeyCode::Period,
+            53 => KeyCode::Slash,
+            54 => KeyCode::RightShift,
+            55 => KeyCode::NumpadMultiply,
+            56 => KeyCode::LeftAlt,
+            57 => KeyCode::Space,
+            58 => KeyCode::CapsLock,
+            59 => KeyCode::F1,
+            60 => KeyCode::F2,
+            61 => KeyCode::F3,
+            62 => KeyCode::F4,
+            63 => KeyCode::F5,
+            64 => KeyCode::F6,
+            65 => KeyCode::F7,
+            66 => KeyCode::F8,
+            67 => KeyCode::F9,
+            68 => KeyCode::F10,
+            69 => KeyCode::NumLock,
+            70 => KeyCode::ScrollLock,
+            71 => KeyCode::Numpad7,
+            72 => KeyCode::Numpad8,
+            73 => KeyCode::Numpad9,
+            74 => KeyCode::NumpadSubtract,
+            75 => KeyCode::Numpad4,
+            76 => KeyCode::Numpad5,
+            77 => KeyCode::Numpad6,
+            78 => KeyCode::NumpadAdd,
+            79 => KeyCode::Numpad1,
+            80 => KeyCode::Numpad2,
+            81 => KeyCode::Numpad3,
+            82 => KeyCode::Numpad0,
+            83 => KeyCode::NumpadDecimal,
+            87 => KeyCode::F11,
+            88 => KeyCode::F12,
+            96 => KeyCode::Enter,
+            97 => KeyCode::RightControl,
+            98 => KeyCode::NumpadDivide,
+            100 => KeyCode::RightAlt,
+            102 => KeyCode::Home,
+            103 => KeyCode::ArrowUp,
+            104 => KeyCode::PageUp,
+            105 => KeyCode::ArrowLeft,
+            106 => KeyCode::ArrowRight,
+            107 => KeyCode::End,
+            108 => KeyCode::ArrowDown,
+            109 => KeyCode::PageDown,
+            110 => KeyCode::Insert,
+            111 => KeyCode::Delete,
+            125 => KeyCode::LeftWin,
+            126 => KeyCode::RightWin,
+            183 => KeyCode::F13,
+            184 => KeyCode::F14,
+            185 => KeyCode::F15,
+            186 => KeyCode::F16,
+            187 => KeyCode::F17,
+            188 => KeyCode::F18,
+            189 => KeyCode::F19,
+            190 => KeyCode::F20,
+            191 => KeyCode::F21,
+            192 => KeyCode::F22,
+            193 => KeyCode::F23,
+            194 => KeyCode::F24,
+            _ => KeyCode::Unrecognized,
+        }
+    }
+}
@@ -1,11 +1,25 @@
 mod error;
+mod events;
 mod globals;
+mod hotkey;
+mod key_code;
 mod traits;
+#[cfg(unix)]
+mod unix;
+#[cfg(windows)]
+mod raw_input;
+#[cfg(windows)]
 mod windows;
 
 pub mod hid_monitor;
 
 pub use error::{Error, Result};
+pub use events::{ButtonState, KeyboardEvent, MouseButton, MouseEvent};
+#[cfg(windows)]
+pub use hid_monitor::Backend;
 pub use hid_monitor::{HidCallback, HidMonitor, HidType};
-pub use traits::Call;
+pub use hotkey::{Hotkey, HotkeyId, Modifiers};
+pub use key_code::KeyCode;
+pub use traits::{Call, Propagation};
+#[cfg(windows)]
 pub use windows::message_loop;
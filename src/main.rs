@@ -1,6 +1,7 @@
 use std::sync::{Arc, Mutex};
 
-use hidmon::{Call, HidCallback, HidMonitor, HidType};
+use hidmon::{Call, HidCallback, HidMonitor, HidType, MouseEvent, Propagation};
+#[cfg(windows)]
 use windows::Win32::Foundation::{LPARAM, WPARAM};
 
 #[derive(Default)]
@@ -9,10 +10,18 @@ struct MyType1 {
 }
 
 impl Call for MyType1 {
-    fn callback(&mut self, n_code: i32, w_param: WPARAM, l_param: LPARAM) {
+    #[cfg(windows)]
+    fn callback(&mut self, n_code: i32, w_param: WPARAM, l_param: LPARAM) -> Propagation {
         println!("[MyType1, call #{}]:", self.call_counter);
         println!("\tn_code: {n_code}, w_param: {w_param:?}, l_param: {l_param:?}");
         self.call_counter += 1;
+        Propagation::Pass
+    }
+
+    fn on_mouse(&mut self, event: MouseEvent) -> Propagation {
+        println!("[MyType1, call #{}]: {event:?}", self.call_counter);
+        self.call_counter += 1;
+        Propagation::Pass
     }
 }
 
@@ -26,10 +35,18 @@ struct MyType2 {
 unsafe impl Send for MyType2 {}
 
 impl Call for MyType2 {
-    fn callback(&mut self, n_code: i32, w_param: WPARAM, l_param: LPARAM) {
+    #[cfg(windows)]
+    fn callback(&mut self, n_code: i32, w_param: WPARAM, l_param: LPARAM) -> Propagation {
         println!("[MyType2, call #{}]:", self.call_counter);
         println!("\tn_code: {n_code}, w_param: {w_param:?}, l_param: {l_param:?}");
         self.call_counter -= 1;
+        Propagation::Pass
+    }
+
+    fn on_mouse(&mut self, event: MouseEvent) -> Propagation {
+        println!("[MyType2, call #{}]: {event:?}", self.call_counter);
+        self.call_counter -= 1;
+        Propagation::Pass
     }
 }
 
@@ -51,5 +68,13 @@ fn main() {
         .expect("Error enabling mouse monitoring for MyType2");
 
     // Convience function for handling WinApi messages
+    #[cfg(windows)]
     hidmon::message_loop();
+
+    // No platform message loop on Linux; evdev is read from its own background thread, so just
+    // keep the process alive.
+    #[cfg(unix)]
+    loop {
+        std::thread::park();
+    }
 }
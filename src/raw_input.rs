@@ -0,0 +1,235 @@
+#![allow(non_snake_case)]
+use std::mem::size_of;
+
+use windows::core::w;
+use windows::Win32::Foundation::{HWND, LPARAM, LRESULT, WPARAM};
+use windows::Win32::System::LibraryLoader::GetModuleHandleW;
+use windows::Win32::UI::Input::{
+    GetRawInputData, RegisterRawInputDevices, HRAWINPUT, RAWINPUT, RAWINPUTDEVICE,
+    RAWINPUTHEADER, RAWKEYBOARD, RAWMOUSE, RID_INPUT, RIDEV_INPUTSINK, RIDEV_REMOVE,
+    RIM_TYPEKEYBOARD, RIM_TYPEMOUSE,
+};
+use windows::Win32::UI::WindowsAndMessaging::{
+    CreateWindowExW, DefWindowProcW, DestroyWindow, RegisterClassExW, UnregisterClassW,
+    CW_USEDEFAULT, HWND_MESSAGE, WINDOW_EX_STYLE, WM_INPUT, WNDCLASSEXW, WS_OVERLAPPED,
+};
+
+use crate::{
+    events::{ButtonState, KeyboardEvent, MouseButton, MouseEvent},
+    globals::{GLOBAL_KEYBD_CALLBACKS, GLOBAL_MOUSE_CALLBACKS},
+    key_code::KeyCode,
+    traits::Propagation,
+    Result,
+};
+
+const WINDOW_CLASS_NAME: windows::core::PCWSTR = w!("HidMonRawInputWindow");
+
+// RI_KEY_BREAK: set in RAWKEYBOARD::Flags when the key transitioned to "up"
+const RI_KEY_BREAK: u16 = 0x01;
+
+// RI_MOUSE_* button transition flags, from RAWMOUSE::usButtonFlags
+const RI_MOUSE_LEFT_BUTTON_DOWN: u16 = 0x0001;
+const RI_MOUSE_LEFT_BUTTON_UP: u16 = 0x0002;
+const RI_MOUSE_RIGHT_BUTTON_DOWN: u16 = 0x0004;
+const RI_MOUSE_RIGHT_BUTTON_UP: u16 = 0x0008;
+const RI_MOUSE_MIDDLE_BUTTON_DOWN: u16 = 0x0010;
+const RI_MOUSE_MIDDLE_BUTTON_UP: u16 = 0x0020;
+const RI_MOUSE_WHEEL: u16 = 0x0400;
+
+/// Registers raw input for the generic-desktop keyboard (usage 0x06) and mouse (usage 0x02)
+/// collections, delivering `WM_INPUT` to `hwnd` even while it is not focused
+pub(crate) fn register_devices(hwnd: HWND) -> Result<()> {
+    let devices = [
+        RAWINPUTDEVICE {
+            usUsagePage: 0x01,
+            usUsage: 0x06,
+            dwFlags: RIDEV_INPUTSINK,
+            hwndTarget: hwnd,
+        },
+        RAWINPUTDEVICE {
+            usUsagePage: 0x01,
+            usUsage: 0x02,
+            dwFlags: RIDEV_INPUTSINK,
+            hwndTarget: hwnd,
+        },
+    ];
+    unsafe { RegisterRawInputDevices(&devices, size_of::<RAWINPUTDEVICE>() as u32)? };
+    Ok(())
+}
+
+/// Unregisters the device classes registered by [`register_devices`]
+fn unregister_devices() -> Result<()> {
+    let devices = [
+        RAWINPUTDEVICE {
+            usUsagePage: 0x01,
+            usUsage: 0x06,
+            dwFlags: RIDEV_REMOVE,
+            hwndTarget: HWND::default(),
+        },
+        RAWINPUTDEVICE {
+            usUsagePage: 0x01,
+            usUsage: 0x02,
+            dwFlags: RIDEV_REMOVE,
+            hwndTarget: HWND::default(),
+        },
+    ];
+    unsafe { RegisterRawInputDevices(&devices, size_of::<RAWINPUTDEVICE>() as u32)? };
+    Ok(())
+}
+
+/// Creates a hidden message-only window (a child of `HWND_MESSAGE`) to receive `WM_INPUT`
+pub(crate) fn create_message_window() -> Result<HWND> {
+    unsafe {
+        let instance = GetModuleHandleW(None)?;
+        let class = WNDCLASSEXW {
+            cbSize: size_of::<WNDCLASSEXW>() as u32,
+            lpfnWndProc: Some(raw_input_wndproc),
+            hInstance: instance.into(),
+            lpszClassName: WINDOW_CLASS_NAME,
+            ..Default::default()
+        };
+        // Registering the class twice (e.g. a second `HidMonitor`) is harmless; ignore the error
+        let _ = RegisterClassExW(&class);
+        let hwnd = CreateWindowExW(
+            WINDOW_EX_STYLE::default(),
+            WINDOW_CLASS_NAME,
+            w!("hidmon raw input"),
+            WS_OVERLAPPED,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            CW_USEDEFAULT,
+            HWND_MESSAGE,
+            None,
+            Some(instance.into()),
+            None,
+        )?;
+        Ok(hwnd)
+    }
+}
+
+/// Tears down the window created by [`create_message_window`], unregistering raw input first
+pub(crate) fn destroy_message_window(hwnd: HWND) -> Result<()> {
+    unregister_devices()?;
+    unsafe {
+        DestroyWindow(hwnd)?;
+        let _ = UnregisterClassW(WINDOW_CLASS_NAME, None);
+    }
+    Ok(())
+}
+
+unsafe extern "system" fn raw_input_wndproc(
+    hwnd: HWND,
+    message: u32,
+    wparam: WPARAM,
+    lparam: LPARAM,
+) -> LRESULT {
+    if message == WM_INPUT {
+        handle_wm_input(lparam);
+        return LRESULT(0);
+    }
+    DefWindowProcW(hwnd, message, wparam, lparam)
+}
+
+unsafe fn handle_wm_input(lparam: LPARAM) {
+    let header_size = size_of::<RAWINPUTHEADER>() as u32;
+    let mut size = 0u32;
+    GetRawInputData(HRAWINPUT(lparam.0), RID_INPUT, None, &mut size, header_size);
+    if size == 0 {
+        return;
+    }
+    let mut buffer = vec![0u8; size as usize];
+    let read = GetRawInputData(
+        HRAWINPUT(lparam.0),
+        RID_INPUT,
+        Some(buffer.as_mut_ptr().cast()),
+        &mut size,
+        header_size,
+    );
+    if read == u32::MAX || read as usize != buffer.len() {
+        return;
+    }
+    let raw = &*(buffer.as_ptr().cast::<RAWINPUT>());
+    if raw.header.dwType == RIM_TYPEKEYBOARD.0 {
+        dispatch_keyboard(&raw.data.keyboard);
+    } else if raw.header.dwType == RIM_TYPEMOUSE.0 {
+        dispatch_mouse(&raw.data.mouse);
+    }
+}
+
+fn decode_keyboard(kbd: &RAWKEYBOARD) -> KeyboardEvent {
+    let key = KeyCode::from(kbd.VKey as u32);
+    if kbd.Flags & RI_KEY_BREAK != 0 {
+        KeyboardEvent::KeyUp {
+            key,
+            scan_code: kbd.MakeCode as u32,
+            flags: kbd.Flags as u32,
+            time: 0,
+        }
+    } else {
+        KeyboardEvent::KeyDown {
+            key,
+            scan_code: kbd.MakeCode as u32,
+            flags: kbd.Flags as u32,
+            time: 0,
+        }
+    }
+}
+
+fn dispatch_keyboard(kbd: &RAWKEYBOARD) {
+    let event = decode_keyboard(kbd);
+    let callback_map = GLOBAL_KEYBD_CALLBACKS.lock().unwrap();
+    for entry in callback_map.values() {
+        let mut call = entry.0.lock().unwrap();
+        // No raw WPARAM/LPARAM exists for this backend; the typed event is the only signal
+        let _: Propagation = call.on_keyboard(event);
+    }
+}
+
+fn decode_mouse(mouse: &RAWMOUSE) -> Vec<MouseEvent> {
+    let mut events = Vec::new();
+    if mouse.lLastX != 0 || mouse.lLastY != 0 {
+        events.push(MouseEvent::Move {
+            x: mouse.lLastX,
+            y: mouse.lLastY,
+        });
+    }
+    let (button_flags, button_data) = unsafe {
+        (
+            mouse.Anonymous.Anonymous.usButtonFlags,
+            mouse.Anonymous.Anonymous.usButtonData,
+        )
+    };
+    if button_flags & RI_MOUSE_WHEEL != 0 {
+        events.push(MouseEvent::Wheel {
+            delta: button_data as i16,
+        });
+    }
+    for (flag, button, state) in [
+        (RI_MOUSE_LEFT_BUTTON_DOWN, MouseButton::Left, ButtonState::Down),
+        (RI_MOUSE_LEFT_BUTTON_UP, MouseButton::Left, ButtonState::Up),
+        (RI_MOUSE_RIGHT_BUTTON_DOWN, MouseButton::Right, ButtonState::Down),
+        (RI_MOUSE_RIGHT_BUTTON_UP, MouseButton::Right, ButtonState::Up),
+        (RI_MOUSE_MIDDLE_BUTTON_DOWN, MouseButton::Middle, ButtonState::Down),
+        (RI_MOUSE_MIDDLE_BUTTON_UP, MouseButton::Middle, ButtonState::Up),
+    ] {
+        if button_flags & flag != 0 {
+            events.push(MouseEvent::Button {
+                button,
+                state,
+                time: 0,
+            });
+        }
+    }
+    events
+}
+
+fn dispatch_mouse(mouse: &RAWMOUSE) {
+    let callback_map = GLOBAL_MOUSE_CALLBACKS.lock().unwrap();
+    for event in decode_mouse(mouse) {
+        for entry in callback_map.values() {
+            let mut call = entry.0.lock().unwrap();
+            let _: Propagation = call.on_mouse(event);
+        }
+    }
+}
@@ -0,0 +1,60 @@
+#[cfg(windows)]
+use windows::Win32::Foundation::{LPARAM, WPARAM};
+
+use crate::events::{KeyboardEvent, MouseEvent};
+
+/// Whether an event should continue on to the rest of the system, or be swallowed
+///
+/// Returned by [`Call`]'s methods; if any registered callback returns [`Propagation::Block`]
+/// for a given event, the hook proc suppresses it instead of calling `CallNextHookEx`.
+///
+/// Only honored under [`crate::Backend::Hook`]. [`crate::Backend::RawInput`] and the Linux
+/// `evdev` backend have no mechanism to suppress an event from reaching the rest of the system,
+/// so a [`Propagation::Block`] returned from a callback is silently ignored under either.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Propagation {
+    #[default]
+    Pass,
+    Block,
+}
+
+impl Propagation {
+    /// Combines two results, such that [`Propagation::Block`] wins over [`Propagation::Pass`]
+    pub(crate) fn and(self, other: Self) -> Self {
+        match (self, other) {
+            (Propagation::Block, _) | (_, Propagation::Block) => Propagation::Block,
+            (Propagation::Pass, Propagation::Pass) => Propagation::Pass,
+        }
+    }
+}
+
+/// Implemented by types that want to observe HID events delivered through a [`crate::HidMonitor`]
+///
+/// ## ⚠️ Warning
+///
+/// Every method here is invoked while this callback's own registry lock is held (and, under
+/// [`crate::Backend::Hook`], while pumping the `WH_KEYBOARD_LL`/`WH_MOUSE_LL` hook). Do not call
+/// back into `HidMonitor` synchronously from inside one of these methods (or a
+/// [`crate::HidMonitor::register_hotkey`] callback) — e.g. `enable`, `disable`,
+/// `register_hotkey`, or `unregister_hotkey` — as re-locking the same mutex from the same thread
+/// deadlocks it, which under `Backend::Hook` freezes system-wide keyboard/mouse input until
+/// Windows force-detaches the hook. Defer any such call to another thread instead.
+pub trait Call {
+    /// Called with the raw hook parameters for every event of the registered `HidType`
+    ///
+    /// Only available on Windows, where a raw `WPARAM`/`LPARAM` pair actually exists; other
+    /// backends (Linux `evdev`, or the Windows Raw Input backend) only have a decoded event, so
+    /// they call [`Call::on_keyboard`]/[`Call::on_mouse`] alone.
+    #[cfg(windows)]
+    fn callback(&mut self, n_code: i32, w_param: WPARAM, l_param: LPARAM) -> Propagation;
+
+    /// Called with a decoded keyboard event, in addition to [`Call::callback`] where available
+    fn on_keyboard(&mut self, _event: KeyboardEvent) -> Propagation {
+        Propagation::Pass
+    }
+
+    /// Called with a decoded mouse event, in addition to [`Call::callback`] where available
+    fn on_mouse(&mut self, _event: MouseEvent) -> Propagation {
+        Propagation::Pass
+    }
+}
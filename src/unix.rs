@@ -0,0 +1,225 @@
+use std::fs::File;
+use std::io::Read;
+use std::mem::size_of;
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::{Arc, Mutex};
+use std::thread::JoinHandle;
+
+use crate::events::{ButtonState, KeyboardEvent, MouseButton, MouseEvent};
+use crate::globals::{GLOBAL_KEYBD_CALLBACKS, GLOBAL_MOUSE_CALLBACKS};
+use crate::key_code::KeyCode;
+use crate::traits::Propagation;
+use crate::Result;
+
+// linux/input-event-codes.h
+const EV_SYN: u16 = 0x00;
+const EV_KEY: u16 = 0x01;
+const EV_REL: u16 = 0x02;
+const SYN_REPORT: u16 = 0x00;
+const REL_X: u16 = 0x00;
+const REL_Y: u16 = 0x01;
+const REL_WHEEL: u16 = 0x08;
+const BTN_LEFT: u16 = 0x110;
+const BTN_RIGHT: u16 = 0x111;
+const BTN_MIDDLE: u16 = 0x112;
+
+#[repr(C)]
+struct TimeVal {
+    tv_sec: i64,
+    tv_usec: i64,
+}
+
+/// Mirrors `struct input_event` from `linux/input.h`
+#[repr(C)]
+struct InputEvent {
+    time: TimeVal,
+    kind: u16,
+    code: u16,
+    value: i32,
+}
+
+struct Reader {
+    shutdown: Arc<AtomicBool>,
+    handle: JoinHandle<()>,
+}
+
+static REFCOUNT: Mutex<usize> = Mutex::new(0);
+static READER: Mutex<Option<Reader>> = Mutex::new(None);
+
+/// Starts the shared `evdev` reader thread if it isn't already running
+///
+/// Reference-counted across both `HidType`s, the same way [`crate::raw_input`]'s message window
+/// is shared between keyboard and mouse on Windows; the underlying thread is only spawned once no
+/// matter how many times `start` is called, and only torn down once every caller has [`stop`]ped.
+pub(crate) fn start() -> Result<()> {
+    let mut refcount = REFCOUNT.lock().unwrap();
+    *refcount += 1;
+    if *refcount > 1 {
+        return Ok(());
+    }
+    let shutdown = Arc::new(AtomicBool::new(false));
+    let thread_shutdown = shutdown.clone();
+    let handle = std::thread::spawn(move || read_loop(thread_shutdown));
+    *READER.lock().unwrap() = Some(Reader { shutdown, handle });
+    Ok(())
+}
+
+/// Releases one reference taken by [`start`], stopping and joining the reader thread once the
+/// last reference is released
+pub(crate) fn stop() {
+    let mut refcount = REFCOUNT.lock().unwrap();
+    if *refcount == 0 {
+        return;
+    }
+    *refcount -= 1;
+    if *refcount > 0 {
+        return;
+    }
+    if let Some(reader) = READER.lock().unwrap().take() {
+        reader.shutdown.store(true, Ordering::Relaxed);
+        let _ = reader.handle.join();
+    }
+}
+
+/// Opens every `/dev/input/event*` device and spawns a blocking reader thread per device,
+/// joining all of them before returning
+fn read_loop(shutdown: Arc<AtomicBool>) {
+    let mut devices = Vec::new();
+    if let Ok(entries) = std::fs::read_dir("/dev/input") {
+        for entry in entries.flatten() {
+            let is_event_device = entry
+                .file_name()
+                .to_str()
+                .is_some_and(|name| name.starts_with("event"));
+            if is_event_device {
+                if let Ok(file) = File::open(entry.path()) {
+                    devices.push(file);
+                }
+            }
+        }
+    }
+    let handles: Vec<_> = devices
+        .into_iter()
+        .map(|device| {
+            let shutdown = shutdown.clone();
+            std::thread::spawn(move || read_device(device, shutdown))
+        })
+        .collect();
+    for handle in handles {
+        let _ = handle.join();
+    }
+}
+
+/// Accumulates a device's `EV_REL` X/Y motion between `SYN_REPORT`s, so it can be flushed as one
+/// combined [`MouseEvent::Move`] instead of one per axis
+#[derive(Default)]
+struct PendingMotion {
+    dx: i32,
+    dy: i32,
+}
+
+/// Reads `input_event`s from a single device, dispatching each until the device closes or
+/// `shutdown` is set
+///
+/// Uses a blocking read per device rather than `epoll`; since each device gets its own thread,
+/// shutdown is only checked between events, not mid-read.
+fn read_device(mut device: File, shutdown: Arc<AtomicBool>) {
+    let mut buf = [0u8; size_of::<InputEvent>()];
+    let mut pending_motion = PendingMotion::default();
+    while !shutdown.load(Ordering::Relaxed) {
+        if device.read_exact(&mut buf).is_err() {
+            break;
+        }
+        // SAFETY: `buf` holds exactly `size_of::<InputEvent>()` bytes read from the kernel, which
+        // lays out `struct input_event` as a plain, packed-compatible sequence of integers.
+        let event = unsafe { std::ptr::read(buf.as_ptr().cast::<InputEvent>()) };
+        dispatch(&event, &mut pending_motion);
+    }
+}
+
+/// Maps a `BTN_LEFT`/`BTN_RIGHT`/`BTN_MIDDLE` evdev code to a normalized [`MouseButton`]
+fn decode_mouse_button(code: u16) -> Option<MouseButton> {
+    match code {
+        BTN_LEFT => Some(MouseButton::Left),
+        BTN_RIGHT => Some(MouseButton::Right),
+        BTN_MIDDLE => Some(MouseButton::Middle),
+        _ => None,
+    }
+}
+
+/// Dispatches a single `input_event`
+///
+/// `EV_REL` X/Y motion is accumulated into `pending_motion` rather than dispatched immediately;
+/// it's only flushed as a combined [`MouseEvent::Move`] on `SYN_REPORT`, matching how
+/// `Backend::Hook` and `Backend::RawInput` each report one `Move` per reporting interval rather
+/// than one per axis.
+fn dispatch(event: &InputEvent, pending_motion: &mut PendingMotion) {
+    match event.kind {
+        EV_KEY => dispatch_key(event),
+        EV_REL => match event.code {
+            REL_X => pending_motion.dx += event.value,
+            REL_Y => pending_motion.dy += event.value,
+            // evdev reports one unit per wheel notch; scale to `WHEEL_DELTA` (120) per notch
+            REL_WHEEL => dispatch_mouse(MouseEvent::Wheel {
+                delta: (event.value * 120) as i16,
+            }),
+            _ => {}
+        },
+        EV_SYN if event.code == SYN_REPORT => {
+            if pending_motion.dx != 0 || pending_motion.dy != 0 {
+                dispatch_mouse(MouseEvent::Move {
+                    x: pending_motion.dx,
+                    y: pending_motion.dy,
+                });
+            }
+            pending_motion.dx = 0;
+            pending_motion.dy = 0;
+        }
+        _ => {}
+    }
+}
+
+fn dispatch_key(event: &InputEvent) {
+    if let Some(button) = decode_mouse_button(event.code) {
+        let state = if event.value == 0 {
+            ButtonState::Up
+        } else {
+            ButtonState::Down
+        };
+        dispatch_mouse(MouseEvent::Button {
+            button,
+            state,
+            time: 0,
+        });
+        return;
+    }
+    let key = KeyCode::from_linux_keycode(event.code);
+    let keyboard_event = if event.value == 0 {
+        KeyboardEvent::KeyUp {
+            key,
+            scan_code: event.code as u32,
+            flags: 0,
+            time: 0,
+        }
+    } else {
+        KeyboardEvent::KeyDown {
+            key,
+            scan_code: event.code as u32,
+            flags: 0,
+            time: 0,
+        }
+    };
+    let callback_map = GLOBAL_KEYBD_CALLBACKS.lock().unwrap();
+    for entry in callback_map.values() {
+        let mut call = entry.0.lock().unwrap();
+        let _: Propagation = call.on_keyboard(keyboard_event);
+    }
+}
+
+fn dispatch_mouse(event: MouseEvent) {
+    let callback_map = GLOBAL_MOUSE_CALLBACKS.lock().unwrap();
+    for entry in callback_map.values() {
+        let mut call = entry.0.lock().unwrap();
+        let _: Propagation = call.on_mouse(event);
+    }
+}
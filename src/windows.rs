@@ -1,18 +1,117 @@
 #![allow(non_snake_case)]
+use std::sync::Mutex;
+
 use windows::Win32::{
-    Foundation::{LPARAM, LRESULT, WPARAM},
+    Foundation::{LPARAM, LRESULT, POINT, WPARAM},
+    System::Threading::GetCurrentThreadId,
     UI::WindowsAndMessaging::{
-        CallNextHookEx, DispatchMessageW, GetMessageW, SetWindowsHookExW, TranslateMessage,
-        UnhookWindowsHookEx, HHOOK, HOOKPROC, MSG, WH_KEYBOARD_LL, WH_MOUSE_LL, WINDOWS_HOOK_ID,
-        WM_QUIT,
+        CallNextHookEx, DispatchMessageW, GetMessageW, PostThreadMessageW, SetWindowsHookExW,
+        TranslateMessage, UnhookWindowsHookEx, HHOOK, HOOKPROC, KBDLLHOOKSTRUCT, MSG,
+        MSLLHOOKSTRUCT, WH_KEYBOARD_LL, WH_MOUSE_LL, WINDOWS_HOOK_ID, WM_KEYDOWN, WM_KEYUP,
+        WM_LBUTTONDOWN, WM_LBUTTONUP, WM_MBUTTONDOWN, WM_MBUTTONUP, WM_MOUSEMOVE, WM_MOUSEWHEEL,
+        WM_QUIT, WM_RBUTTONDOWN, WM_RBUTTONUP, WM_SYSKEYDOWN, WM_SYSKEYUP, WM_XBUTTONDOWN,
+        WM_XBUTTONUP,
     },
 };
 
 use crate::{
+    events::{ButtonState, KeyboardEvent, MouseButton, MouseEvent},
     globals::{GLOBAL_KEYBD_CALLBACKS, GLOBAL_MOUSE_CALLBACKS},
+    key_code::KeyCode,
+    traits::Propagation,
     HidType, Result,
 };
 
+// XBUTTON1/XBUTTON2, packed into the high word of `MSLLHOOKSTRUCT::mouseData` for
+// WM_XBUTTONDOWN/WM_XBUTTONUP
+const XBUTTON1: u16 = 0x0001;
+const XBUTTON2: u16 = 0x0002;
+
+/// Tracks the last absolute cursor position reported by `WM_MOUSEMOVE`, so [`decode_mouse_event`]
+/// can report relative deltas like every other backend
+static LAST_MOUSE_POS: Mutex<Option<(i32, i32)>> = Mutex::new(None);
+
+/// Maps a `WM_*BUTTON*` message (plus `MSLLHOOKSTRUCT::mouseData`, needed to disambiguate
+/// `WM_XBUTTONDOWN`/`WM_XBUTTONUP`) to a normalized button/state pair
+fn decode_mouse_button(message: u32, mouse_data: u32) -> Option<(MouseButton, ButtonState)> {
+    match message {
+        WM_LBUTTONDOWN => Some((MouseButton::Left, ButtonState::Down)),
+        WM_LBUTTONUP => Some((MouseButton::Left, ButtonState::Up)),
+        WM_RBUTTONDOWN => Some((MouseButton::Right, ButtonState::Down)),
+        WM_RBUTTONUP => Some((MouseButton::Right, ButtonState::Up)),
+        WM_MBUTTONDOWN => Some((MouseButton::Middle, ButtonState::Down)),
+        WM_MBUTTONUP => Some((MouseButton::Middle, ButtonState::Up)),
+        WM_XBUTTONDOWN | WM_XBUTTONUP => {
+            let state = if message == WM_XBUTTONDOWN {
+                ButtonState::Down
+            } else {
+                ButtonState::Up
+            };
+            let button = match ((mouse_data >> 16) & 0xffff) as u16 {
+                XBUTTON1 => MouseButton::X1,
+                XBUTTON2 => MouseButton::X2,
+                _ => return None,
+            };
+            Some((button, state))
+        }
+        _ => None,
+    }
+}
+
+/// Dereferences the `KBDLLHOOKSTRUCT` pointed to by `lparam` and maps it to a [`KeyboardEvent`],
+/// or `None` if `wparam` is not a message this crate decodes
+unsafe fn decode_keyboard_event(wparam: WPARAM, lparam: LPARAM) -> Option<KeyboardEvent> {
+    let kbd = &*(lparam.0 as *const KBDLLHOOKSTRUCT);
+    let key = KeyCode::from(kbd.vkCode);
+    match wparam.0 as u32 {
+        WM_KEYDOWN | WM_SYSKEYDOWN => Some(KeyboardEvent::KeyDown {
+            key,
+            scan_code: kbd.scanCode,
+            flags: kbd.flags.0,
+            time: kbd.time,
+        }),
+        WM_KEYUP | WM_SYSKEYUP => Some(KeyboardEvent::KeyUp {
+            key,
+            scan_code: kbd.scanCode,
+            flags: kbd.flags.0,
+            time: kbd.time,
+        }),
+        _ => None,
+    }
+}
+
+/// Dereferences the `MSLLHOOKSTRUCT` pointed to by `lparam` and maps it to a [`MouseEvent`],
+/// or `None` if `wparam` is not a message this crate decodes
+unsafe fn decode_mouse_event(wparam: WPARAM, lparam: LPARAM) -> Option<MouseEvent> {
+    let ms = &*(lparam.0 as *const MSLLHOOKSTRUCT);
+    match wparam.0 as u32 {
+        WM_MOUSEMOVE => {
+            let POINT { x, y } = ms.pt;
+            let mut last_pos = LAST_MOUSE_POS.lock().unwrap();
+            let (dx, dy) = match *last_pos {
+                Some((last_x, last_y)) => (x - last_x, y - last_y),
+                None => (0, 0),
+            };
+            *last_pos = Some((x, y));
+            Some(MouseEvent::Move { x: dx, y: dy })
+        }
+        WM_MOUSEWHEEL => Some(MouseEvent::Wheel {
+            delta: ((ms.mouseData >> 16) & 0xffff) as i16,
+        }),
+        WM_LBUTTONDOWN | WM_LBUTTONUP | WM_RBUTTONDOWN | WM_RBUTTONUP | WM_MBUTTONDOWN
+        | WM_MBUTTONUP | WM_XBUTTONDOWN | WM_XBUTTONUP => {
+            decode_mouse_button(wparam.0 as u32, ms.mouseData).map(|(button, state)| {
+                MouseEvent::Button {
+                    button,
+                    state,
+                    time: ms.time,
+                }
+            })
+        }
+        _ => None,
+    }
+}
+
 pub unsafe extern "system" fn LowLevelKeyboardProc(
     ncode: i32,
     wparam: WPARAM,
@@ -21,11 +120,20 @@ pub unsafe extern "system" fn LowLevelKeyboardProc(
     if ncode < 0 {
         return CallNextHookEx(None, ncode, wparam, lparam);
     }
+    let event = decode_keyboard_event(wparam, lparam);
+    let mut propagation = Propagation::Pass;
     let callback_map = GLOBAL_KEYBD_CALLBACKS.lock().unwrap();
     for entry in &mut callback_map.values() {
-        entry.0.lock().unwrap().callback(ncode, wparam, lparam);
+        let mut call = entry.0.lock().unwrap();
+        propagation = propagation.and(call.callback(ncode, wparam, lparam));
+        if let Some(event) = event {
+            propagation = propagation.and(call.on_keyboard(event));
+        }
     }
     drop(callback_map);
+    if propagation == Propagation::Block {
+        return LRESULT(1);
+    }
     CallNextHookEx(None, ncode, wparam, lparam)
 }
 
@@ -37,11 +145,20 @@ pub unsafe extern "system" fn LowLevelMouseProc(
     if ncode < 0 {
         return CallNextHookEx(None, ncode, wparam, lparam);
     }
+    let event = decode_mouse_event(wparam, lparam);
+    let mut propagation = Propagation::Pass;
     let callback_map = GLOBAL_MOUSE_CALLBACKS.lock().unwrap();
     for entry in &mut callback_map.values() {
-        entry.0.lock().unwrap().callback(ncode, wparam, lparam);
+        let mut call = entry.0.lock().unwrap();
+        propagation = propagation.and(call.callback(ncode, wparam, lparam));
+        if let Some(event) = event {
+            propagation = propagation.and(call.on_mouse(event));
+        }
     }
     drop(callback_map);
+    if propagation == Propagation::Block {
+        return LRESULT(1);
+    }
     CallNextHookEx(None, ncode, wparam, lparam)
 }
 
@@ -60,6 +177,17 @@ pub fn unhook(hook: HHOOK) -> Result<()> {
     unsafe { Ok(UnhookWindowsHookEx(hook)?) }
 }
 
+/// Returns the WinAPI thread id of the calling thread
+pub fn current_thread_id() -> u32 {
+    unsafe { GetCurrentThreadId() }
+}
+
+/// Posts `WM_QUIT` to the given thread's message queue, causing a blocked [`message_loop`] call
+/// on that thread to return
+pub fn post_quit(thread_id: u32) -> Result<()> {
+    unsafe { Ok(PostThreadMessageW(thread_id, WM_QUIT, WPARAM(0), LPARAM(0))?) }
+}
+
 impl From<HidType> for HOOKPROC {
     fn from(value: HidType) -> Self {
         match value {